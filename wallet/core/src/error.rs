@@ -0,0 +1,21 @@
+//!
+//! Wallet-core error type.
+//!
+
+use crate::tx::generator::policy::TransferPolicyError;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Custom(String),
+
+    #[error(transparent)]
+    TransferPolicy(#[from] TransferPolicyError),
+}
+
+impl Error {
+    pub fn custom(msg: impl Into<String>) -> Self {
+        Self::Custom(msg.into())
+    }
+}
@@ -0,0 +1,20 @@
+//!
+//! Wallet-core events published on the `Events` [`Multiplexer`](workflow_core::channel::Multiplexer)
+//! configured on [`GeneratorSettings`](crate::tx::generator::GeneratorSettings).
+//!
+
+use crate::tx::Fees;
+
+/// Wallet-core events published on the wallet's `Events` multiplexer.
+#[derive(Clone, Debug)]
+pub enum Events {
+    /// Progress of a bulk disbursement started via
+    /// [`GeneratorSettings::try_new_with_batch`](crate::tx::generator::GeneratorSettings::try_new_with_batch):
+    /// `committed` recipients have been packed into `transaction_index + 1`
+    /// transactions so far.
+    BatchDisbursementProgress { committed: usize, transaction_index: usize },
+    /// The effective fee the [`Generator`](crate::tx::generator::Generator)
+    /// resolved for the transaction at `transaction_index`, once its final
+    /// mass was known, so callers can audit what was actually paid.
+    TransactionFeeResolved { transaction_index: usize, fee: Fees },
+}
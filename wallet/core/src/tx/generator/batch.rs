@@ -0,0 +1,47 @@
+//!
+//! Bulk disbursement support for the [`Generator`](super::Generator).
+//!
+//! Lets a caller hand over a large (possibly streamed) set of `(Address, u64)`
+//! payouts instead of pre-slicing them into individual transactions. The
+//! [`Generator`] greedily packs recipients from the batch into each
+//! transaction it produces, up to a caller-supplied recipient cap, spilling
+//! the remainder into subsequent transactions.
+//!
+
+use crate::imports::*;
+use crate::result::Result;
+use kaspa_addresses::Address;
+
+/// A single recipient entry within a bulk disbursement batch.
+pub type BatchOutput = (Address, u64);
+
+/// Bulk disbursement configuration carried by
+/// [`GeneratorSettings`](super::GeneratorSettings).
+///
+/// Wraps a (potentially unbounded) iterator of `(Address, u64)` payout pairs
+/// together with `recipient_cap`, the maximum number of recipients the
+/// [`Generator`](super::Generator) is allowed to pack into a single
+/// transaction. The generator will additionally respect the standard
+/// transaction mass limit, whichever bound is hit first.
+pub struct BatchDisbursement {
+    pub(crate) outputs: Box<dyn Iterator<Item = BatchOutput> + Send + Sync + 'static>,
+    pub(crate) recipient_cap: usize,
+}
+
+impl BatchDisbursement {
+    /// Create a batch disbursement from an in-memory list of recipients.
+    /// Returns an error if `recipient_cap` is zero.
+    pub fn new(outputs: Vec<BatchOutput>, recipient_cap: usize) -> Result<Self> {
+        Self::new_with_iterator(outputs.into_iter(), recipient_cap)
+    }
+
+    /// Create a batch disbursement from a streaming iterator of recipients,
+    /// avoiding the need to materialize the full recipient set in memory.
+    /// Returns an error if `recipient_cap` is zero.
+    pub fn new_with_iterator(outputs: impl Iterator<Item = BatchOutput> + Send + Sync + 'static, recipient_cap: usize) -> Result<Self> {
+        if recipient_cap == 0 {
+            return Err(Error::custom("batch disbursement recipient_cap must be greater than zero"));
+        }
+        Ok(Self { outputs: Box::new(outputs), recipient_cap })
+    }
+}
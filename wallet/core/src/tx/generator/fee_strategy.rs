@@ -0,0 +1,55 @@
+//!
+//! Pluggable fee-rate estimation and priority-fee strategies for the
+//! [`Generator`](super::Generator).
+//!
+//! Unlike a fixed [`Fees`] value, a [`FeeStrategy`] is resolved once the
+//! [`Generator`](super::Generator) knows the final mass of the transaction
+//! being built, so the caller does not need to guess a fee before mass is
+//! known. The resolved fee for each generated transaction is surfaced
+//! through the settings' `Events` multiplexer.
+//!
+
+use crate::result::Result;
+use crate::tx::Fees;
+use std::sync::Arc;
+
+/// Supplies the current network feerate (fee per unit of transaction mass)
+/// used to resolve [`FeeStrategy::Target`].
+pub trait FeeRateEstimator: Send + Sync {
+    fn feerate(&self) -> Result<f64>;
+}
+
+/// A strategy for deriving `final_transaction_priority_fee` once a
+/// transaction's mass is known, instead of supplying a fixed [`Fees`] value
+/// up front.
+pub enum FeeStrategy {
+    /// Use a fixed, caller-supplied fee for every transaction.
+    Fixed(Fees),
+    /// Charge `rate` sompi per unit of computed transaction mass.
+    PerMassUnit(u64),
+    /// Charge a fee that achieves `feerate` sompi per unit of mass. When a
+    /// [`FeeRateEstimator`] is supplied at resolution time it takes priority
+    /// over `feerate`, which is then used only as the fallback rate when no
+    /// estimator is available.
+    Target { feerate: f64 },
+}
+
+impl FeeStrategy {
+    /// Resolve this strategy into a concrete [`Fees`] value now that the
+    /// transaction's `mass` is known.
+    pub fn resolve(&self, mass: u64, estimator: Option<&dyn FeeRateEstimator>) -> Result<Fees> {
+        match self {
+            Self::Fixed(fees) => Ok(fees.clone()),
+            Self::PerMassUnit(rate) => Ok(Fees::SenderPays(rate.saturating_mul(mass))),
+            Self::Target { feerate } => {
+                let feerate = match estimator {
+                    Some(estimator) => estimator.feerate()?,
+                    None => *feerate,
+                };
+                Ok(Fees::SenderPays((feerate * mass as f64).round() as u64))
+            }
+        }
+    }
+}
+
+pub type FeeRateEstimatorRef = Arc<dyn FeeRateEstimator>;
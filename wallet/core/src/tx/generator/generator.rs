@@ -0,0 +1,185 @@
+//!
+//! [`Generator`] turns [`GeneratorSettings`] into a sequence of transactions,
+//! greedily packing bulk-disbursement recipients across as many transactions
+//! as necessary and publishing progress through the settings' `Events`
+//! multiplexer.
+//!
+
+use crate::events::Events;
+use crate::imports::*;
+use crate::result::Result;
+use crate::tx::generator::batch::BatchDisbursement;
+use crate::tx::generator::mass::{estimate_transaction_mass, STANDARD_TRANSACTION_MASS_LIMIT};
+use crate::tx::generator::policy::TransferPolicy;
+use crate::tx::generator::settings::GeneratorSettings;
+use crate::tx::Fees;
+use kaspa_addresses::Address;
+
+/// A single transaction produced by the [`Generator`], prior to UTXO
+/// selection and signing.
+#[derive(Debug, Clone)]
+pub struct GeneratorTransaction {
+    pub outputs: Vec<(Address, u64)>,
+    pub change_address: Address,
+    pub mass: u64,
+    pub fee: Fees,
+}
+
+/// Produces a sequence of [`GeneratorTransaction`]s from [`GeneratorSettings`].
+pub struct Generator {
+    settings: GeneratorSettings,
+}
+
+impl Generator {
+    pub fn try_new(settings: GeneratorSettings) -> Result<Self> {
+        Ok(Self { settings })
+    }
+
+    pub fn settings(&self) -> &GeneratorSettings {
+        &self.settings
+    }
+
+    /// Produce the sequence of transactions for these settings. When
+    /// `batch_disbursement` is present, recipients are greedily packed into
+    /// transactions up to `recipient_cap` and [`STANDARD_TRANSACTION_MASS_LIMIT`],
+    /// spilling the remainder into subsequent transactions.
+    pub fn generate(&mut self) -> Result<Vec<GeneratorTransaction>> {
+        match self.settings.batch_disbursement.take() {
+            Some(batch) => self.generate_batch(batch),
+            None => self.generate_single(),
+        }
+    }
+
+    fn generate_single(&mut self) -> Result<Vec<GeneratorTransaction>> {
+        let outputs: Vec<(Address, u64)> = match &self.settings.final_transaction_destination {
+            PaymentDestination::Change => vec![],
+            PaymentDestination::PaymentOutputs(outputs) => outputs.0.iter().map(|output| (output.address.clone(), output.amount)).collect(),
+        };
+
+        self.settings.transfer_policy.check(&self.settings.change_address)?;
+        for (address, _) in &outputs {
+            self.settings.transfer_policy.check(address)?;
+        }
+
+        let mass = estimate_transaction_mass(&outputs, &self.settings.change_address);
+        let fee = self.resolve_fee(mass, 0)?;
+
+        Ok(vec![GeneratorTransaction { outputs, change_address: self.settings.change_address.clone(), mass, fee }])
+    }
+
+    fn generate_batch(&mut self, batch: BatchDisbursement) -> Result<Vec<GeneratorTransaction>> {
+        let BatchDisbursement { outputs, recipient_cap } = batch;
+        let mut transactions = Vec::new();
+        let mut pending = Vec::new();
+        let mut committed = 0usize;
+
+        for output in outputs {
+            self.settings.transfer_policy.check(&output.0)?;
+            pending.push(output);
+
+            // If the newest recipient would push this transaction over the mass
+            // limit, finalize without it and carry it into the next transaction.
+            if pending.len() > 1 && estimate_transaction_mass(&pending, &self.settings.change_address) > STANDARD_TRANSACTION_MASS_LIMIT {
+                let spilled = pending.pop().expect("pending holds at least two recipients");
+                self.finalize_batch_transaction(&mut transactions, &mut pending, &mut committed)?;
+                pending.push(spilled);
+            }
+
+            if pending.len() >= recipient_cap {
+                self.finalize_batch_transaction(&mut transactions, &mut pending, &mut committed)?;
+            }
+        }
+
+        if !pending.is_empty() {
+            self.finalize_batch_transaction(&mut transactions, &mut pending, &mut committed)?;
+        }
+
+        Ok(transactions)
+    }
+
+    fn finalize_batch_transaction(
+        &self,
+        transactions: &mut Vec<GeneratorTransaction>,
+        pending: &mut Vec<(Address, u64)>,
+        committed: &mut usize,
+    ) -> Result<()> {
+        self.settings.transfer_policy.check(&self.settings.change_address)?;
+
+        let outputs = std::mem::take(pending);
+        let mass = estimate_transaction_mass(&outputs, &self.settings.change_address);
+        *committed += outputs.len();
+        let fee = self.resolve_fee(mass, transactions.len())?;
+
+        transactions.push(GeneratorTransaction { outputs, change_address: self.settings.change_address.clone(), mass, fee });
+
+        self.notify(Events::BatchDisbursementProgress { committed: *committed, transaction_index: transactions.len() - 1 })?;
+
+        Ok(())
+    }
+
+    /// Resolve the effective fee for the transaction at `transaction_index`
+    /// given its final `mass`. When `fee_strategy` is set it takes priority
+    /// over the fixed `final_transaction_priority_fee`, consulting
+    /// `fee_rate_estimator` for [`FeeStrategy::Target`]. The resolved fee is
+    /// published on the `Events` multiplexer so callers can audit what was
+    /// actually paid.
+    fn resolve_fee(&self, mass: u64, transaction_index: usize) -> Result<Fees> {
+        let fee = match &self.settings.fee_strategy {
+            Some(strategy) => strategy.resolve(mass, self.settings.fee_rate_estimator.as_deref())?,
+            None => self.settings.final_transaction_priority_fee.clone(),
+        };
+
+        self.notify(Events::TransactionFeeResolved { transaction_index, fee: fee.clone() })?;
+
+        Ok(fee)
+    }
+
+    /// Publish `event` on the settings' `Events` multiplexer, if one is configured.
+    fn notify(&self, event: Events) -> Result<()> {
+        if let Some(multiplexer) = &self.settings.multiplexer {
+            multiplexer.broadcast(Box::new(event)).map_err(|err| Error::custom(err.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tx::{PaymentOutput, PaymentOutputs};
+    use kaspa_addresses::{Prefix, Version};
+    use std::collections::HashSet;
+
+    fn addr(byte: u8) -> Address {
+        Address::new(Prefix::Testnet, Version::PubKey, &[byte; 32])
+    }
+
+    fn settings_with_policy(transfer_policy: TransferPolicy) -> GeneratorSettings {
+        GeneratorSettings::try_new_with_iterator(
+            Box::new(std::iter::empty()),
+            addr(0),
+            1,
+            1,
+            PaymentDestination::PaymentOutputs(PaymentOutputs(vec![PaymentOutput { address: addr(1), amount: 100 }])),
+            Fees::None,
+            None,
+            None,
+            transfer_policy,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn disallowed_destination_fails_fast() {
+        let allowed = HashSet::from([addr(0)]);
+        let mut generator = Generator::try_new(settings_with_policy(TransferPolicy::AllowList(allowed))).unwrap();
+        assert!(generator.generate().is_err(), "output address not in the allowlist must be rejected");
+    }
+
+    #[test]
+    fn allowed_destinations_succeed() {
+        let allowed = HashSet::from([addr(0), addr(1)]);
+        let mut generator = Generator::try_new(settings_with_policy(TransferPolicy::AllowList(allowed))).unwrap();
+        assert!(generator.generate().is_ok());
+    }
+}
@@ -0,0 +1,48 @@
+//!
+//! Transaction mass estimation for the [`Generator`](super::Generator).
+//!
+//! Mirrors the consensus mass formula (`mass_per_tx_byte`, `mass_per_script_pub_key_byte`)
+//! against the real serialized size of each output's `scriptPubKey`, rather
+//! than a flat per-output estimate, so fee strategies and the batch packing
+//! cap are resolved against mass that tracks what the network will actually
+//! charge. Input mass and KIP-9 storage mass are not modeled here, as UTXO
+//! selection has not yet happened at the point the [`Generator`] packs
+//! outputs.
+//!
+
+use kaspa_addresses::Address;
+use kaspa_txscript::pay_to_address_script;
+
+/// Mass charged per byte of transaction data (version, lock time, gas,
+/// subnetwork id, payload, and each output's value/script-version/length
+/// fields).
+const MASS_PER_TX_BYTE: u64 = 1;
+/// Additional mass charged per byte of `scriptPubKey`, reflecting its cost
+/// to UTXO-set storage.
+const MASS_PER_SCRIPT_PUB_KEY_BYTE: u64 = 10;
+/// Serialized size, in bytes, of the fields shared by every transaction
+/// (version, lock time, gas, subnetwork id, input/output counts, empty
+/// payload) excluding outputs.
+const BASE_TRANSACTION_SERIALIZED_BYTES: u64 = 60;
+/// Per-output fixed serialized overhead: 8-byte value + 2-byte script
+/// version + script length varint.
+const OUTPUT_FIXED_SERIALIZED_BYTES: u64 = 11;
+
+/// Standard per-transaction mass limit enforced by consensus.
+pub const STANDARD_TRANSACTION_MASS_LIMIT: u64 = 100_000;
+
+/// Mass contribution of a single output paying to `address`; the amount
+/// does not affect mass.
+fn output_mass(address: &Address) -> u64 {
+    let script_public_key = pay_to_address_script(address);
+    let script_bytes = script_public_key.script().len() as u64;
+    MASS_PER_TX_BYTE * (OUTPUT_FIXED_SERIALIZED_BYTES + script_bytes) + MASS_PER_SCRIPT_PUB_KEY_BYTE * script_bytes
+}
+
+/// Estimate the mass of a transaction paying `outputs` plus a single
+/// `change_address` output.
+pub fn estimate_transaction_mass(outputs: &[(Address, u64)], change_address: &Address) -> u64 {
+    let base = MASS_PER_TX_BYTE * BASE_TRANSACTION_SERIALIZED_BYTES;
+    let outputs_mass: u64 = outputs.iter().map(|(address, _amount)| output_mass(address)).sum();
+    base + outputs_mass + output_mass(change_address)
+}
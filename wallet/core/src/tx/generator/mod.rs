@@ -0,0 +1,17 @@
+//!
+//! Transaction [`Generator`] and its [`GeneratorSettings`].
+//!
+
+pub mod batch;
+pub mod fee_strategy;
+pub mod generator;
+pub mod mass;
+pub mod policy;
+pub mod settings;
+
+pub use batch::{BatchDisbursement, BatchOutput};
+pub use fee_strategy::{FeeRateEstimator, FeeRateEstimatorRef, FeeStrategy};
+pub use generator::{Generator, GeneratorTransaction};
+pub use mass::STANDARD_TRANSACTION_MASS_LIMIT;
+pub use policy::{TransferPolicy, TransferPolicyError};
+pub use settings::GeneratorSettings;
@@ -0,0 +1,70 @@
+//!
+//! Destination allowlist / transfer restriction policy consulted by the
+//! [`Generator`](super::Generator) for every resolved output address.
+//!
+
+use crate::imports::*;
+use crate::result::Result;
+use kaspa_addresses::Address;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Restricts which destinations a [`Generator`](super::Generator) is allowed
+/// to pay to. Consulted for every resolved output address of a transaction,
+/// including the `change_address`, before the transaction is built.
+#[derive(Clone)]
+pub enum TransferPolicy {
+    /// No restriction; any destination address is permitted.
+    Unrestricted,
+    /// Only addresses present in the set are permitted.
+    AllowList(HashSet<Address>),
+    /// A caller-supplied predicate decides whether a destination is permitted.
+    Predicate(Arc<dyn Fn(&Address) -> bool + Send + Sync>),
+}
+
+impl TransferPolicy {
+    /// Returns `true` if `address` is permitted under this policy.
+    pub fn is_allowed(&self, address: &Address) -> bool {
+        match self {
+            Self::Unrestricted => true,
+            Self::AllowList(allowed) => allowed.contains(address),
+            Self::Predicate(predicate) => predicate(address),
+        }
+    }
+
+    /// Validates `address` against this policy, failing fast with a
+    /// dedicated [`Error::TransferPolicy`] carrying a [`TransferPolicyError`]
+    /// if the destination is disallowed, so callers can programmatically
+    /// distinguish a policy rejection from any other error.
+    pub fn check(&self, address: &Address) -> Result<()> {
+        if self.is_allowed(address) {
+            Ok(())
+        } else {
+            Err(TransferPolicyError::DisallowedDestination(address.clone()).into())
+        }
+    }
+}
+
+impl Default for TransferPolicy {
+    fn default() -> Self {
+        Self::Unrestricted
+    }
+}
+
+impl fmt::Debug for TransferPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unrestricted => write!(f, "TransferPolicy::Unrestricted"),
+            Self::AllowList(allowed) => f.debug_tuple("TransferPolicy::AllowList").field(allowed).finish(),
+            Self::Predicate(_) => write!(f, "TransferPolicy::Predicate(..)"),
+        }
+    }
+}
+
+/// Error raised when a transaction destination is rejected by the active
+/// [`TransferPolicy`].
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TransferPolicyError {
+    #[error("destination {0} is not permitted by the active transfer policy")]
+    DisallowedDestination(Address),
+}
@@ -6,9 +6,14 @@
 use crate::events::Events;
 use crate::imports::*;
 use crate::result::Result;
+use crate::tx::generator::batch::BatchDisbursement;
+use crate::tx::generator::fee_strategy::{FeeRateEstimatorRef, FeeStrategy};
+use crate::tx::generator::policy::TransferPolicy;
+use crate::tx::memo::{Memo, MemoPayload};
 use crate::tx::{Fees, PaymentDestination};
 use crate::utxo::{UtxoContext, UtxoEntryReference, UtxoIterator};
 use kaspa_addresses::Address;
+use secp256k1::PublicKey;
 use workflow_core::channel::Multiplexer;
 
 pub struct GeneratorSettings {
@@ -34,6 +39,17 @@ pub struct GeneratorSettings {
     pub final_transaction_payload: Option<Vec<u8>>,
     // transaction is a transfer between accounts
     pub destination_utxo_context: Option<UtxoContext>,
+    // bulk disbursement to a large number of recipients, packed
+    // across as many transactions as necessary
+    pub batch_disbursement: Option<BatchDisbursement>,
+    // destination allowlist / transfer restriction policy, checked against
+    // every resolved output address (including the change address)
+    pub transfer_policy: TransferPolicy,
+    // optional fee-rate strategy resolved against the final transaction
+    // mass, overriding `final_transaction_priority_fee` when present
+    pub fee_strategy: Option<FeeStrategy>,
+    // fee-rate estimator consulted when resolving `FeeStrategy::Target`
+    pub fee_rate_estimator: Option<FeeRateEstimatorRef>,
 }
 
 impl GeneratorSettings {
@@ -42,6 +58,7 @@ impl GeneratorSettings {
         final_transaction_destination: PaymentDestination,
         final_priority_fee: Fees,
         final_transaction_payload: Option<Vec<u8>>,
+        transfer_policy: TransferPolicy,
     ) -> Result<Self> {
         let network_type = account.utxo_context().processor().network_id()?.into();
         let change_address = account.change_address()?;
@@ -64,6 +81,10 @@ impl GeneratorSettings {
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
+            batch_disbursement: None,
+            transfer_policy,
+            fee_strategy: None,
+            fee_rate_estimator: None,
         };
 
         Ok(settings)
@@ -78,6 +99,7 @@ impl GeneratorSettings {
         final_priority_fee: Fees,
         final_transaction_payload: Option<Vec<u8>>,
         multiplexer: Option<Multiplexer<Box<Events>>>,
+        transfer_policy: TransferPolicy,
     ) -> Result<Self> {
         let network_type = utxo_context.processor().network_id()?.into();
         let utxo_iterator = UtxoIterator::new(&utxo_context);
@@ -95,6 +117,10 @@ impl GeneratorSettings {
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
+            batch_disbursement: None,
+            transfer_policy,
+            fee_strategy: None,
+            fee_rate_estimator: None,
         };
 
         Ok(settings)
@@ -109,6 +135,7 @@ impl GeneratorSettings {
         final_priority_fee: Fees,
         final_transaction_payload: Option<Vec<u8>>,
         multiplexer: Option<Multiplexer<Box<Events>>>,
+        transfer_policy: TransferPolicy,
     ) -> Result<Self> {
         let network_type = NetworkType::try_from(change_address.prefix)?;
 
@@ -125,6 +152,10 @@ impl GeneratorSettings {
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
+            batch_disbursement: None,
+            transfer_policy,
+            fee_strategy: None,
+            fee_rate_estimator: None,
         };
 
         Ok(settings)
@@ -134,4 +165,70 @@ impl GeneratorSettings {
         self.destination_utxo_context = Some(destination_utxo_context.clone());
         self
     }
+
+    /// Create settings for a bulk disbursement to a large number of recipients
+    /// (e.g. staking reward payouts or airdrops). `recipients` does not need to
+    /// fit within a single transaction's mass or size limits: the
+    /// [`Generator`](crate::tx::Generator) packs up to `recipient_cap` outputs
+    /// into each transaction it produces, using `change_address` and
+    /// `final_priority_fee` for every transaction in the resulting sequence,
+    /// and spills the remainder into subsequent transactions until all
+    /// recipients have been paid.
+    pub fn try_new_with_batch(
+        account: Arc<dyn Account>,
+        recipients: Vec<(Address, u64)>,
+        recipient_cap: usize,
+        final_priority_fee: Fees,
+        final_transaction_payload: Option<Vec<u8>>,
+    ) -> Result<Self> {
+        let mut settings = Self::try_new_with_account(
+            account,
+            PaymentDestination::Change,
+            final_priority_fee,
+            final_transaction_payload,
+            TransferPolicy::default(),
+        )?;
+        settings.batch_disbursement = Some(BatchDisbursement::new(recipients, recipient_cap)?);
+        Ok(settings)
+    }
+
+    /// Create settings carrying an optional structured recipient [`Memo`] in
+    /// place of a raw `final_transaction_payload`. When `recipient_public_key`
+    /// is supplied, the memo is encrypted for that recipient via ephemeral-key
+    /// ECDH before being placed in the transaction payload; otherwise it is
+    /// carried as plaintext. See [`MemoPayload`](crate::tx::memo::MemoPayload).
+    pub fn try_new_with_account_memo(
+        account: Arc<dyn Account>,
+        final_transaction_destination: PaymentDestination,
+        final_priority_fee: Fees,
+        memo: Option<Memo>,
+        recipient_public_key: Option<PublicKey>,
+    ) -> Result<Self> {
+        let final_transaction_payload =
+            memo.map(|memo| MemoPayload::seal(memo, recipient_public_key.as_ref())).transpose()?.map(|payload| payload.to_payload_bytes());
+
+        Self::try_new_with_account(account, final_transaction_destination, final_priority_fee, final_transaction_payload, TransferPolicy::default())
+    }
+
+    /// Create settings driven by a [`FeeStrategy`] instead of a fixed
+    /// `final_priority_fee`. The strategy is resolved by the
+    /// [`Generator`](crate::tx::Generator) against each transaction's final
+    /// mass once it is known, consulting `fee_rate_estimator` for
+    /// [`FeeStrategy::Target`]; the resolved fee for every generated
+    /// transaction is surfaced through the `Events` multiplexer.
+    pub fn try_new_with_account_fee_strategy(
+        account: Arc<dyn Account>,
+        final_transaction_destination: PaymentDestination,
+        final_transaction_payload: Option<Vec<u8>>,
+        fee_strategy: FeeStrategy,
+        fee_rate_estimator: Option<FeeRateEstimatorRef>,
+    ) -> Result<Self> {
+        // `final_priority_fee` is superseded by `fee_strategy` once resolved
+        // per-transaction; `Fees::None` is a neutral placeholder until then.
+        let mut settings =
+            Self::try_new_with_account(account, final_transaction_destination, Fees::None, final_transaction_payload, TransferPolicy::default())?;
+        settings.fee_strategy = Some(fee_strategy);
+        settings.fee_rate_estimator = fee_rate_estimator;
+        Ok(settings)
+    }
 }